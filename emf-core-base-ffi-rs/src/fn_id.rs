@@ -50,4 +50,5 @@ pub enum FnId {
     LibraryUnload = 215,
     LibraryGetDataSymbol = 216,
     LibraryGetFunctionSymbol = 217,
+    LibraryLoadWithFlags = 218,
 }
@@ -1,6 +1,7 @@
 use crate::library::{
-    LibraryError, LibraryHandle, LibraryHandleRef, LibraryLoaderHandle, LibraryLoaderHandleRef,
-    LibraryLoaderWrapper, LibrarySymbol, LibraryType, LoaderLibraryHandle, LoaderLibraryHandleRef,
+    LibraryError, LibraryHandle, LibraryHandleRef, LibraryLoadFlags, LibraryLoaderHandle,
+    LibraryLoaderHandleRef, LibraryLoaderWrapper, LibrarySymbol, LibraryType, LoaderLibraryHandle,
+    LoaderLibraryHandleRef, OwnedLibrary, OwnedLoader, ReloadableLibraryHandle, SymbolBundle,
 };
 use crate::{ffi, FFIObject};
 use std::ffi::CStr;
@@ -58,6 +59,21 @@ pub trait LibraryToken<'a> {
     /// ```
     fn unregister_loader(&self, loader: LibraryLoaderHandle) -> Option<LibraryError>;
 
+    /// Registers a new `LibraryLoader`, returning a guard that unregisters
+    /// it on [Drop](std::ops::Drop) instead of requiring a matching call to
+    /// [LibraryToken::unregister_loader()].
+    ///
+    /// # Failure
+    ///
+    /// The function fails if the library type already exists.
+    fn register_loader_scoped<'c, T: LibraryLoaderWrapper<'static>>(
+        &'c self,
+        loader: &T,
+        lib_type: &LibraryType,
+    ) -> Result<OwnedLoader<'c, Self>, LibraryError>
+    where
+        Self: Sized + LibraryToken<'c>;
+
     /// Fetches the number of registered loaders.
     fn get_num_loaders(&self) -> usize;
 
@@ -171,7 +187,10 @@ pub trait LibraryToken<'a> {
 
     /// Loads a library from a path.
     ///
-    /// The resulting handle is unique.
+    /// The resulting handle is unique. Equivalent to calling
+    /// [LibraryToken::load_with_flags()] with [LibraryLoadFlags::DEFAULT]
+    /// (`RTLD_NOW | RTLD_LOCAL` on Unix), preserving the behavior of this
+    /// method from before flags existed.
     ///
     /// # Failure
     ///
@@ -183,6 +202,25 @@ pub trait LibraryToken<'a> {
         path: &T,
     ) -> Result<LibraryHandle<'c>, LibraryError>;
 
+    /// Loads a library from a path, giving control over how the loader
+    /// opens it.
+    ///
+    /// `flags` is a portable bitset that is translated to the closest
+    /// matching `dlopen` flags on Unix or `LoadLibraryExW` flags on Windows
+    /// (see [LibraryLoadFlags]); flags with no equivalent on the current
+    /// platform are dropped rather than causing the load to fail.
+    ///
+    /// # Failure
+    ///
+    /// The function fails if `loader` or `path` is invalid or the type
+    /// of the library can not be loaded by the loader.
+    fn load_with_flags<'c, 'b: 'c, T: AsRef<Path>>(
+        &self,
+        loader: &'b LibraryLoaderHandleRef<'b>,
+        path: &T,
+        flags: LibraryLoadFlags,
+    ) -> Result<LibraryHandle<'c>, LibraryError>;
+
     /// Unloads a library.
     ///
     /// # Failure
@@ -190,6 +228,47 @@ pub trait LibraryToken<'a> {
     /// The function fails if `library` is invalid.
     fn unload(&self, library: LibraryHandle) -> Option<LibraryError>;
 
+    /// Loads a library from a path, returning a guard that unloads it on
+    /// [Drop](std::ops::Drop) instead of requiring a matching call to
+    /// [LibraryToken::unload()].
+    ///
+    /// The returned [OwnedLibrary] borrows `self` for as long as it is
+    /// alive, so `get_data_symbol`/`get_function_symbol` symbols fetched
+    /// through it (see [OwnedLibrary::get_data_symbol()],
+    /// [OwnedLibrary::get_function_symbol()]) cannot dangle past the point
+    /// the library is unloaded.
+    ///
+    /// # Failure
+    ///
+    /// The function fails if `loader` or `path` is invalid or the type
+    /// of the library can not be loaded by the loader.
+    fn load_scoped<'c, 'b: 'c, T: AsRef<Path>>(
+        &'c self,
+        loader: &'b LibraryLoaderHandleRef<'b>,
+        path: &T,
+    ) -> Result<OwnedLibrary<'c, Self>, LibraryError>
+    where
+        Self: Sized + LibraryToken<'c>;
+
+    /// Loads a library from a path, allowing it to be reloaded later on.
+    ///
+    /// Unlike [LibraryToken::load()], the target file is first copied to a
+    /// uniquely-named file inside the system temp directory and the loader
+    /// opens *that* copy, leaving `path` itself free to be rebuilt in place.
+    /// Call [ReloadableLibraryHandle::reload()] to pick up a new build once
+    /// `path` has changed.
+    ///
+    /// # Failure
+    ///
+    /// The function fails if `loader` or `path` is invalid, the type of the
+    /// library can not be loaded by the loader, or the temp copy could not
+    /// be created.
+    fn load_reloadable<'c, 'b: 'c, T: AsRef<Path>>(
+        &'c self,
+        loader: &'b LibraryLoaderHandleRef<'b>,
+        path: &T,
+    ) -> Result<ReloadableLibraryHandle<'c>, LibraryError>;
+
     /// Fetches a data symbol from a library.
     ///
     /// Some platforms may differentiate between a `function-pointer` and a `data-pointer`.
@@ -217,4 +296,24 @@ pub trait LibraryToken<'a> {
         library: &'b LibraryHandleRef<'b>,
         name: &S,
     ) -> Result<LibrarySymbol<'b, T>, LibraryError>;
+
+    /// Resolves a fixed bundle of symbols from a library in one call.
+    ///
+    /// `B` is typically a struct of [LibrarySymbol]s implementing
+    /// [SymbolBundle], one field per entry point a plugin ABI requires. This
+    /// mirrors how plugin hosts validate an ABI surface up front: the
+    /// returned error distinguishes an invalid `library` from a specific
+    /// missing symbol, instead of the opaque failure a one-symbol-at-a-time
+    /// lookup would give.
+    ///
+    /// # Failure
+    ///
+    /// The function fails if `library` is invalid, or if any of the symbols
+    /// `B` requires is absent.
+    fn resolve_symbols<'b, B: SymbolBundle<'b>>(
+        &self,
+        library: &'b LibraryHandleRef<'b>,
+    ) -> Result<B, LibraryError>
+    where
+        Self: LibraryToken<'b>;
 }
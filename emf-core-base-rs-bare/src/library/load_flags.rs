@@ -0,0 +1,171 @@
+//! Portable flags controlling how a library is opened by the loader.
+use std::ops::{BitOr, BitOrAssign};
+
+/// Portable flags passed to [LibraryToken::load_with_flags](crate::library::LibraryToken::load_with_flags).
+///
+/// Each flag maps to the closest equivalent on the platforms emf-core-base
+/// supports. A flag with no equivalent on the current platform is silently
+/// dropped by [LibraryLoadFlags::to_native()] rather than causing the load
+/// to fail.
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct LibraryLoadFlags(u32);
+
+impl LibraryLoadFlags {
+    /// No flags set.
+    pub const NONE: Self = Self(0);
+
+    /// Resolve symbols lazily, on first use. Unix: `RTLD_LAZY`.
+    pub const LAZY: Self = Self(0x1);
+    /// Resolve all symbols immediately on load. Unix: `RTLD_NOW`.
+    pub const NOW: Self = Self(0x2);
+    /// Make the library's symbols available to libraries loaded afterwards.
+    /// Unix: `RTLD_GLOBAL`.
+    pub const GLOBAL: Self = Self(0x100);
+    /// Keep the library's symbols private to itself. Unix: `RTLD_LOCAL`.
+    /// Takes precedence over [LibraryLoadFlags::GLOBAL] if both are set.
+    pub const LOCAL: Self = Self(0x200);
+    /// Never unload the library, even on a matching number of `dlclose`s.
+    /// Unix: `RTLD_NODELETE`. No effect on Windows.
+    pub const NO_DELETE: Self = Self(0x400);
+    /// Fail instead of loading unless the library is already resident.
+    /// Unix: `RTLD_NOLOAD`. No effect on Windows.
+    pub const NO_LOAD: Self = Self(0x800);
+
+    /// Search the default set of directories for dependencies. Windows:
+    /// `LOAD_LIBRARY_SEARCH_DEFAULT_DIRS`. No effect on Unix.
+    pub const SEARCH_DEFAULT_DIRS: Self = Self(0x1000);
+    /// Map the library as a data file instead of executing its entry point.
+    /// Windows: `LOAD_LIBRARY_AS_DATAFILE`. No effect on Unix.
+    pub const AS_DATAFILE: Self = Self(0x2000);
+    /// Use an altered search path while resolving dependencies. Windows:
+    /// `LOAD_WITH_ALTERED_SEARCH_PATH`. No effect on Unix.
+    pub const ALTERED_SEARCH_PATH: Self = Self(0x4000);
+
+    /// The flags used by [LibraryToken::load](crate::library::LibraryToken::load):
+    /// `NOW | LOCAL`.
+    pub const DEFAULT: Self = Self(Self::NOW.0 | Self::LOCAL.0);
+
+    /// Checks whether `self` contains every flag set in `other`.
+    #[inline]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Translates the portable flags to the `dlopen` flags understood by
+    /// the glibc/Linux loader, dropping flags with no equivalent there.
+    ///
+    /// macOS's `dlfcn.h` assigns different bit values to the same flags
+    /// (and gives `RTLD_LOCAL` an actual bit instead of being the absence of
+    /// `RTLD_GLOBAL`), so it gets its own impl below instead of sharing this
+    /// one under a blanket `#[cfg(unix)]`.
+    #[cfg(all(unix, not(target_os = "macos")))]
+    pub(crate) fn to_native(self) -> std::os::raw::c_int {
+        const RTLD_LAZY: i32 = 0x1;
+        const RTLD_NOW: i32 = 0x2;
+        const RTLD_GLOBAL: i32 = 0x100;
+        const RTLD_LOCAL: i32 = 0;
+        const RTLD_NODELETE: i32 = 0x1000;
+        const RTLD_NOLOAD: i32 = 0x4;
+
+        // RTLD_LOCAL is the absence of RTLD_GLOBAL, so there is no bit to
+        // set for it; LOCAL only has an observable effect when it overrides
+        // a GLOBAL also present in the same set.
+        let mut native = RTLD_LOCAL;
+        if self.contains(Self::LAZY) {
+            native |= RTLD_LAZY;
+        }
+        if self.contains(Self::NOW) {
+            native |= RTLD_NOW;
+        }
+        if self.contains(Self::GLOBAL) && !self.contains(Self::LOCAL) {
+            native |= RTLD_GLOBAL;
+        }
+        if self.contains(Self::NO_DELETE) {
+            native |= RTLD_NODELETE;
+        }
+        if self.contains(Self::NO_LOAD) {
+            native |= RTLD_NOLOAD;
+        }
+        native
+    }
+
+    /// Translates the portable flags to the `dlopen` flags understood by
+    /// the macOS loader, dropping flags with no macOS equivalent.
+    #[cfg(target_os = "macos")]
+    pub(crate) fn to_native(self) -> std::os::raw::c_int {
+        const RTLD_LAZY: i32 = 0x1;
+        const RTLD_NOW: i32 = 0x2;
+        const RTLD_LOCAL: i32 = 0x4;
+        const RTLD_GLOBAL: i32 = 0x8;
+        const RTLD_NOLOAD: i32 = 0x10;
+        const RTLD_NODELETE: i32 = 0x80;
+
+        // Unlike glibc, RTLD_LOCAL is a real bit here, so it is set
+        // directly rather than inferred from the absence of RTLD_GLOBAL.
+        let mut native = 0;
+        if self.contains(Self::LAZY) {
+            native |= RTLD_LAZY;
+        }
+        if self.contains(Self::NOW) {
+            native |= RTLD_NOW;
+        }
+        if self.contains(Self::LOCAL) {
+            native |= RTLD_LOCAL;
+        } else if self.contains(Self::GLOBAL) {
+            native |= RTLD_GLOBAL;
+        }
+        if self.contains(Self::NO_DELETE) {
+            native |= RTLD_NODELETE;
+        }
+        if self.contains(Self::NO_LOAD) {
+            native |= RTLD_NOLOAD;
+        }
+        native
+    }
+
+    /// Translates the portable flags to the `LoadLibraryExW` flags
+    /// understood by the Windows loader, dropping flags with no Windows
+    /// equivalent.
+    #[cfg(windows)]
+    pub(crate) fn to_native(self) -> u32 {
+        const LOAD_LIBRARY_SEARCH_DEFAULT_DIRS: u32 = 0x0000_1000;
+        const LOAD_LIBRARY_AS_DATAFILE: u32 = 0x0000_0002;
+        const LOAD_WITH_ALTERED_SEARCH_PATH: u32 = 0x0000_0008;
+
+        let mut native = 0;
+        if self.contains(Self::SEARCH_DEFAULT_DIRS) {
+            native |= LOAD_LIBRARY_SEARCH_DEFAULT_DIRS;
+        }
+        if self.contains(Self::AS_DATAFILE) {
+            native |= LOAD_LIBRARY_AS_DATAFILE;
+        }
+        if self.contains(Self::ALTERED_SEARCH_PATH) {
+            native |= LOAD_WITH_ALTERED_SEARCH_PATH;
+        }
+        native
+    }
+}
+
+impl Default for LibraryLoadFlags {
+    #[inline]
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl BitOr for LibraryLoadFlags {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for LibraryLoadFlags {
+    #[inline]
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
@@ -0,0 +1,129 @@
+//! RAII guards tying library/loader handle lifetime to Rust scope.
+use crate::library::{
+    LibraryError, LibraryHandle, LibraryLoaderHandle, LibrarySymbol, LibraryToken, LibraryType,
+};
+use crate::{ffi, FFIObject};
+use std::ffi::CStr;
+use std::mem::ManuallyDrop;
+
+/// A [`LibraryHandle`] that unloads itself when dropped.
+///
+/// Returned by [LibraryToken::load_scoped](crate::library::LibraryToken::load_scoped).
+/// Symbols fetched through [`OwnedLibrary::get_data_symbol`] /
+/// [`OwnedLibrary::get_function_symbol`] borrow the guard, so they cannot
+/// dangle past the point the library is unloaded.
+pub struct OwnedLibrary<'a, T: LibraryToken<'a>> {
+    token: &'a T,
+    handle: ManuallyDrop<LibraryHandle<'a>>,
+}
+
+impl<'a, T: LibraryToken<'a>> OwnedLibrary<'a, T> {
+    pub(crate) fn new(token: &'a T, handle: LibraryHandle<'a>) -> Self {
+        Self {
+            token,
+            handle: ManuallyDrop::new(handle),
+        }
+    }
+
+    /// Fetches a data symbol from the library.
+    ///
+    /// See [LibraryToken::get_data_symbol](crate::library::LibraryToken::get_data_symbol).
+    pub fn get_data_symbol<'b, D, S>(
+        &'b self,
+        name: &S,
+    ) -> Result<LibrarySymbol<'b, D>, LibraryError>
+    where
+        D: 'b + Sized + FFIObject<ffi::library::DataSymbol>,
+        S: AsRef<CStr>,
+    {
+        self.token.get_data_symbol(self.handle.as_ref(), name)
+    }
+
+    /// Fetches a function symbol from the library.
+    ///
+    /// See [LibraryToken::get_function_symbol](crate::library::LibraryToken::get_function_symbol).
+    pub fn get_function_symbol<'b, D, S>(
+        &'b self,
+        name: &S,
+    ) -> Result<LibrarySymbol<'b, D>, LibraryError>
+    where
+        D: 'b + Sized + FFIObject<ffi::library::FnSymbol>,
+        S: AsRef<CStr>,
+    {
+        self.token.get_function_symbol(self.handle.as_ref(), name)
+    }
+
+    /// Releases the handle without unloading it, suppressing the [`Drop`]
+    /// impl and returning the raw [`LibraryHandle`] for the caller to
+    /// transfer across an FFI boundary.
+    pub fn into_raw(mut self) -> LibraryHandle<'a> {
+        let handle = unsafe { ManuallyDrop::take(&mut self.handle) };
+        std::mem::forget(self);
+        handle
+    }
+
+    /// Alias for [`OwnedLibrary::into_raw`], for callers who just want to
+    /// stop the guard from unloading the library.
+    pub fn leak(self) -> LibraryHandle<'a> {
+        self.into_raw()
+    }
+}
+
+impl<'a, T: LibraryToken<'a>> Drop for OwnedLibrary<'a, T> {
+    fn drop(&mut self) {
+        let handle = unsafe { ManuallyDrop::take(&mut self.handle) };
+        self.token.unload(handle);
+    }
+}
+
+/// A [`LibraryLoaderHandle`] that unregisters itself when dropped.
+///
+/// Returned by [LibraryToken::register_loader_scoped](crate::library::LibraryToken::register_loader_scoped).
+/// Mirrors [LibraryToken::register_loader](crate::library::LibraryToken::register_loader),
+/// whose handle is always `'static`.
+pub struct OwnedLoader<'a, T: LibraryToken<'a>> {
+    token: &'a T,
+    handle: ManuallyDrop<LibraryLoaderHandle<'static>>,
+    lib_type: LibraryType,
+}
+
+impl<'a, T: LibraryToken<'a>> OwnedLoader<'a, T> {
+    pub(crate) fn new(
+        token: &'a T,
+        handle: LibraryLoaderHandle<'static>,
+        lib_type: LibraryType,
+    ) -> Self {
+        Self {
+            token,
+            handle: ManuallyDrop::new(handle),
+            lib_type,
+        }
+    }
+
+    /// The library type this loader was registered for.
+    pub fn library_type(&self) -> &LibraryType {
+        &self.lib_type
+    }
+
+    /// Releases the handle without unregistering it, suppressing the
+    /// [`Drop`] impl and returning the raw [`LibraryLoaderHandle`] for the
+    /// caller to transfer across an FFI boundary.
+    pub fn into_raw(mut self) -> LibraryLoaderHandle<'static> {
+        let handle = unsafe { ManuallyDrop::take(&mut self.handle) };
+        std::mem::forget(self);
+        handle
+    }
+
+    /// Alias for [`OwnedLoader::into_raw`], for callers who just want to
+    /// stop the guard from unregistering the loader.
+    pub fn leak(self) -> LibraryLoaderHandle<'static> {
+        self.into_raw()
+    }
+}
+
+impl<'a, T: LibraryToken<'a>> Drop for OwnedLoader<'a, T> {
+    fn drop(&mut self) {
+        let handle = unsafe { ManuallyDrop::take(&mut self.handle) };
+        self.token.unregister_loader(handle);
+    }
+}
@@ -0,0 +1,277 @@
+//! Hot-reloading library loader.
+//!
+//! [`ReloadableLibraryHandle`] loads a library from a uniquely-named copy of
+//! the source file placed in a temp directory, rather than from the source
+//! file directly. This leaves the original path free to be overwritten by a
+//! rebuild while the copy stays loaded, and lets [`ReloadableLibraryHandle::reload`]
+//! pick up the new build without the caller ever having to invalidate and
+//! re-fetch a [`LibraryHandle`] by hand.
+use crate::library::{
+    LibraryError, LibraryHandle, LibraryLoaderHandleRef, LibrarySymbol, LibraryToken, SymbolBundle,
+};
+use crate::{ffi, FFIObject};
+use std::ffi::CStr;
+use std::fs;
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::RwLock;
+use std::time::SystemTime;
+
+/// Wraps an I/O failure from [`SourceStamp::read`] or [`fs::copy`] as a
+/// [`LibraryError`], with the library category used for every other fallible
+/// step of (re)loading a library. Failures of [`fs::remove_file`] are
+/// ignored rather than reported.
+///
+/// A local helper rather than a crate-wide `From<io::Error>` impl: every
+/// call site already needs to map the error, so there is no `?`-conversion
+/// to gain, and a blanket impl here would add a crate-wide conversion as a
+/// side effect of this one feature module.
+fn io_error(err: std::io::Error) -> LibraryError {
+    LibraryError::from(ffi::errors::ErrorInfo::from(Box::new(
+        ffi::errors::CausedError::new(
+            "an I/O error occurred while managing a reloadable library's temp copy",
+            err,
+        )
+        .with_category(200),
+    )))
+}
+
+static RELOAD_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Snapshot of the metadata used to detect whether the source file changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SourceStamp {
+    modified: SystemTime,
+    len: u64,
+}
+
+impl SourceStamp {
+    fn read(path: &Path) -> std::io::Result<Self> {
+        let meta = fs::metadata(path)?;
+        Ok(Self {
+            modified: meta.modified()?,
+            len: meta.len(),
+        })
+    }
+}
+
+/// One loaded copy of the source library, and the temp file it was loaded
+/// from.
+///
+/// Does not implement [`Drop`]: unloading the handle requires a
+/// [`LibraryToken`], so retiring a generation is always done explicitly
+/// through [`retire_generation`], both when [`ReloadableLibraryHandle::reload`]
+/// swaps a new generation in and when the handle itself is unloaded.
+struct Generation<'a> {
+    handle: LibraryHandle<'a>,
+    temp_path: PathBuf,
+    stamp: SourceStamp,
+}
+
+fn unique_temp_path(source: &Path) -> PathBuf {
+    let id = RELOAD_COUNTER.fetch_add(1, Ordering::Relaxed);
+    let pid = std::process::id();
+    let nanos = SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+
+    let mut file_name = format!("emf-reload-{pid}-{nanos}-{id}");
+    if let Some(ext) = source.extension().and_then(|ext| ext.to_str()) {
+        file_name.push('.');
+        file_name.push_str(ext);
+    }
+
+    std::env::temp_dir().join(file_name)
+}
+
+fn load_generation<'a, T: LibraryToken<'a>>(
+    token: &T,
+    loader: &LibraryLoaderHandleRef<'a>,
+    source: &Path,
+) -> Result<Generation<'a>, LibraryError> {
+    let stamp = SourceStamp::read(source).map_err(io_error)?;
+    let temp_path = unique_temp_path(source);
+    fs::copy(source, &temp_path).map_err(io_error)?;
+
+    match LibraryToken::load(token, loader, &temp_path) {
+        Ok(handle) => Ok(Generation {
+            handle,
+            temp_path,
+            stamp,
+        }),
+        Err(e) => {
+            let _ = fs::remove_file(&temp_path);
+            Err(e)
+        }
+    }
+}
+
+/// Unloads `generation`'s handle and removes its temp file.
+///
+/// The temp file is removed even if the unload itself fails, so a failed
+/// unload never leaks the copy.
+fn retire_generation<'a, T: LibraryToken<'a>>(
+    token: &T,
+    generation: Generation<'a>,
+) -> Option<LibraryError> {
+    let err = token.unload(generation.handle);
+    let _ = fs::remove_file(&generation.temp_path);
+    err
+}
+
+/// A library handle that can be reloaded from disk while the process keeps
+/// running.
+///
+/// Returned by [`LibraryToken::load_reloadable`]. Every load (the initial
+/// one and every successful [`ReloadableLibraryHandle::reload`]) copies the
+/// source file to a uniquely-named file inside [`std::env::temp_dir()`] and
+/// loads that copy, so the original path is never held open by the loader.
+pub struct ReloadableLibraryHandle<'a> {
+    loader: LibraryLoaderHandleRef<'a>,
+    source: PathBuf,
+    generation: RwLock<Generation<'a>>,
+}
+
+impl<'a> ReloadableLibraryHandle<'a> {
+    pub(crate) fn new<T: LibraryToken<'a>>(
+        token: &T,
+        loader: LibraryLoaderHandleRef<'a>,
+        source: PathBuf,
+    ) -> Result<Self, LibraryError> {
+        let generation = load_generation(token, &loader, &source)?;
+        Ok(Self {
+            loader,
+            source,
+            generation: RwLock::new(generation),
+        })
+    }
+
+    /// Reloads the library if the source file's mtime or size changed since
+    /// it was last loaded.
+    ///
+    /// Returns `Ok(false)` without touching anything if the source file did
+    /// not change. Otherwise a fresh temp copy is made and loaded, and the
+    /// internal handle is swapped atomically; the stale generation is
+    /// unloaded and its temp file removed once every symbol borrowed from it
+    /// (via [`ReloadableLibraryHandle::with_data_symbol`],
+    /// [`ReloadableLibraryHandle::with_function_symbol`] or
+    /// [`ReloadableLibraryHandle::with_symbols`]) has gone out of scope.
+    ///
+    /// This does not re-resolve any symbol set itself: a caller tracking a
+    /// fixed [`SymbolBundle`] just calls [`ReloadableLibraryHandle::with_symbols`]
+    /// again afterwards, which re-validates every symbol the bundle needs
+    /// against the new generation in one call, the same way the initial
+    /// resolution did.
+    ///
+    /// # Failure
+    ///
+    /// The function fails if the source file can no longer be stat'd or
+    /// copied, or if the copy fails to load. It also fails, after the swap
+    /// already took place, if the now-stale generation fails to unload.
+    pub fn reload<T: LibraryToken<'a>>(&self, token: &T) -> Result<bool, LibraryError> {
+        let new_stamp = SourceStamp::read(&self.source).map_err(io_error)?;
+        if new_stamp == self.generation.read().unwrap().stamp {
+            return Ok(false);
+        }
+
+        // Built without holding the write lock, since it involves copying
+        // and loading the library. Another thread may have raced us here
+        // and already swapped in a generation with this exact stamp, so the
+        // stamp is re-checked once the write lock is held below, making the
+        // whole compare-and-swap atomic; if that happened, this generation
+        // was redundant and is retired unused instead of being swapped in.
+        let new_generation = load_generation(token, &self.loader, &self.source)?;
+
+        let mut generation = self.generation.write().unwrap();
+        if generation.stamp == new_stamp {
+            drop(generation);
+            return match retire_generation(token, new_generation) {
+                Some(e) => Err(e),
+                None => Ok(false),
+            };
+        }
+        let old_generation = mem::replace(&mut *generation, new_generation);
+        drop(generation);
+        match retire_generation(token, old_generation) {
+            Some(e) => Err(e),
+            None => Ok(true),
+        }
+    }
+
+    /// Unloads the currently loaded generation and removes its temp file.
+    pub fn unload<T: LibraryToken<'a>>(self, token: &T) -> Option<LibraryError> {
+        retire_generation(token, self.generation.into_inner().unwrap())
+    }
+
+    /// Calls `f` with a data symbol resolved against the currently loaded
+    /// generation.
+    ///
+    /// The symbol cannot escape `f`, which guarantees it is dropped before
+    /// [`ReloadableLibraryHandle::reload`] can swap the generation it was
+    /// resolved from out from under it.
+    pub fn with_data_symbol<T, S, R>(
+        &self,
+        token: &impl LibraryToken<'a>,
+        name: &S,
+        f: impl FnOnce(&LibrarySymbol<'_, T>) -> R,
+    ) -> Result<R, LibraryError>
+    where
+        T: Sized + FFIObject<ffi::library::DataSymbol>,
+        S: AsRef<CStr>,
+    {
+        let generation = self.generation.read().unwrap();
+        let symbol = token.get_data_symbol(generation.handle.as_ref(), name)?;
+        Ok(f(&symbol))
+    }
+
+    /// Calls `f` with a function symbol resolved against the currently
+    /// loaded generation.
+    ///
+    /// The symbol cannot escape `f`, which guarantees it is dropped before
+    /// [`ReloadableLibraryHandle::reload`] can swap the generation it was
+    /// resolved from out from under it.
+    pub fn with_function_symbol<T, S, R>(
+        &self,
+        token: &impl LibraryToken<'a>,
+        name: &S,
+        f: impl FnOnce(&LibrarySymbol<'_, T>) -> R,
+    ) -> Result<R, LibraryError>
+    where
+        T: Sized + FFIObject<ffi::library::FnSymbol>,
+        S: AsRef<CStr>,
+    {
+        let generation = self.generation.read().unwrap();
+        let symbol = token.get_function_symbol(generation.handle.as_ref(), name)?;
+        Ok(f(&symbol))
+    }
+
+    /// Resolves a [`SymbolBundle`] against the currently loaded generation
+    /// and calls `f` with it.
+    ///
+    /// Mirrors [`ReloadableLibraryHandle::with_data_symbol`] /
+    /// [`ReloadableLibraryHandle::with_function_symbol`]: the bundle is
+    /// resolved fresh on every call, so it always matches whichever
+    /// generation happens to be current, and cannot escape `f`, which
+    /// guarantees it is dropped before [`ReloadableLibraryHandle::reload`]
+    /// can swap that generation out from under it.
+    ///
+    /// # Failure
+    ///
+    /// The function fails with a [LibraryError] naming the missing symbol
+    /// if any of `B`'s symbols are absent from the currently loaded
+    /// generation.
+    pub fn with_symbols<B, R>(
+        &self,
+        token: &impl LibraryToken<'a>,
+        f: impl FnOnce(&B) -> R,
+    ) -> Result<R, LibraryError>
+    where
+        B: for<'b> SymbolBundle<'b>,
+    {
+        let generation = self.generation.read().unwrap();
+        let bundle = B::resolve(token, generation.handle.as_ref())?;
+        Ok(f(&bundle))
+    }
+}
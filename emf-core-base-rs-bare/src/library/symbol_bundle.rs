@@ -0,0 +1,28 @@
+//! Resolving a fixed bundle of symbols from a library in one call.
+use crate::library::{LibraryError, LibraryHandleRef, LibraryToken};
+
+/// A fixed bundle of symbols resolved from a single library.
+///
+/// Implemented by a struct of [LibrarySymbol](crate::library::LibrarySymbol)
+/// fields, one per entry point a plugin ABI requires (a version getter, an
+/// info getter, an init function, ...).
+/// [LibraryToken::resolve_symbols()](crate::library::LibraryToken::resolve_symbols)
+/// resolves the whole bundle in one call, reporting exactly which symbol is
+/// missing instead of the opaque "something didn't load" a one-symbol-at-a-
+/// time lookup would give.
+///
+/// All symbols in the bundle share the library handle's lifetime `'b`, so
+/// the bundle cannot outlive the library it was resolved from.
+pub trait SymbolBundle<'b>: Sized {
+    /// Resolves every symbol the bundle needs from `library`.
+    ///
+    /// # Failure
+    ///
+    /// The function fails with a [LibraryError] naming the missing symbol
+    /// if any lookup fails, or propagates the error if `library` itself is
+    /// invalid.
+    fn resolve<T: LibraryToken<'b>>(
+        token: &T,
+        library: &'b LibraryHandleRef<'b>,
+    ) -> Result<Self, LibraryError>;
+}
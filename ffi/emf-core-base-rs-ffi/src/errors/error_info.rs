@@ -1,8 +1,10 @@
 //! Error info type.
 use crate::collections::{ConstSpan, NonNullConst};
 use crate::TypeWrapper;
+use std::collections::HashMap;
 use std::fmt::{Debug, Display, Formatter};
 use std::ptr::NonNull;
+use std::sync::{Arc, Mutex, OnceLock};
 
 /// `UTF-8` error string.
 pub type ErrorString = ConstSpan<u8>;
@@ -21,14 +23,27 @@ pub type CloneFn = TypeWrapper<
 >;
 pub type AsStrFn =
     TypeWrapper<unsafe extern "C-unwind" fn(Option<NonNullConst<ErrorInfoData>>) -> ErrorString>;
+pub type SourceFn = TypeWrapper<
+    unsafe extern "C-unwind" fn(Option<NonNullConst<ErrorInfoData>>) -> Option<ErrorInfo>,
+>;
+pub type CategoryFn =
+    TypeWrapper<unsafe extern "C-unwind" fn(Option<NonNullConst<ErrorInfoData>>) -> i32>;
 
 /// Error vtable.
+///
+/// `source_fn` and `category_fn` were added after the original three slots
+/// and are appended at the end to keep the layout `#[repr(C)]`-stable; both
+/// are nullable so a vtable produced by an older version, or by another
+/// language that never learned about the cause chain, keeps working as a
+/// plain message.
 #[repr(C)]
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
 pub struct ErrorInfoVTable {
     pub cleanup_fn: CleanupFn,
     pub clone_fn: CloneFn,
     pub as_str_fn: AsStrFn,
+    pub source_fn: Option<SourceFn>,
+    pub category_fn: Option<CategoryFn>,
 }
 
 /// Error info.
@@ -45,13 +60,86 @@ impl ErrorInfo {
     pub fn as_str(&self) -> ErrorString {
         unsafe { (self.vtable.as_ref().as_str_fn)(self.data.map(From::from)) }
     }
+
+    /// Fetches the numeric category of the error, reusing the `FnId`-aligned
+    /// ranges (e.g. `200..300` for library errors).
+    ///
+    /// Returns `0` if the vtable predates `category_fn` or the implementor
+    /// did not specify one.
+    #[inline]
+    pub fn category(&self) -> i32 {
+        match unsafe { self.vtable.as_ref() }.category_fn {
+            Some(category_fn) => unsafe { category_fn(self.data.map(From::from)) },
+            None => 0,
+        }
+    }
 }
 
 unsafe impl Send for ErrorInfo {}
 
+/// Materialized `source()` links, keyed by the `data` pointer of the
+/// `ErrorInfo` they were resolved from.
+///
+/// `source_fn` hands back an owned `ErrorInfo`, but `Error::source` must
+/// return a borrow, so the link has to live somewhere with a stable address.
+/// Rather than leaking a fresh one on every call (unbounded over the
+/// lifetime of a long-running host that walks or re-walks a chain), it is
+/// computed once and cached here; [`ErrorInfo::drop`] evicts the entry, so
+/// the cache holds at most one link per currently-live `ErrorInfo` that has
+/// had `source()` called on it. `Box<ErrorInfo>` keeps the link's address
+/// stable across the map's own reallocations.
+fn source_cache() -> &'static Mutex<HashMap<usize, Box<ErrorInfo>>> {
+    static CACHE: OnceLock<Mutex<HashMap<usize, Box<ErrorInfo>>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+impl std::error::Error for ErrorInfo {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        let source_fn = unsafe { self.vtable.as_ref() }.source_fn?;
+
+        // Vtables seen in this crate always pair `source_fn` with `Some`
+        // data, so the `data`-less path below is only ever a defensive
+        // fallback for a hand-written vtable that breaks that pattern.
+        let key = self.data.map(|data| data.as_ptr() as usize);
+        let mut cache = source_cache().lock().unwrap();
+        if let Some(key) = key {
+            if let Some(cached) = cache.get(&key) {
+                let cached: *const ErrorInfo = cached.as_ref();
+                // SAFETY: the entry is only ever removed when `self` (keyed
+                // on the same `data` pointer) is dropped, which can't
+                // happen while this `&self` borrow is held.
+                return Some(unsafe { &*cached });
+            }
+        }
+        drop(cache);
+
+        let next = unsafe { source_fn(self.data.map(From::from)) }?;
+        match key {
+            Some(key) => {
+                let mut cache = source_cache().lock().unwrap();
+                let cached = cache.entry(key).or_insert_with(|| Box::new(next));
+                let cached: *const ErrorInfo = cached.as_ref();
+                Some(unsafe { &*cached })
+            }
+            None => Some(Box::leak(Box::new(next))),
+        }
+    }
+}
+
 impl Drop for ErrorInfo {
     #[inline]
     fn drop(&mut self) {
+        if let Some(data) = self.data {
+            // The removed entry's own `Drop` (it's a cached `ErrorInfo`
+            // itself) re-locks this same, non-reentrant mutex, so it must
+            // run after the guard below is gone rather than as part of the
+            // same statement.
+            let evicted = source_cache()
+                .lock()
+                .unwrap()
+                .remove(&(data.as_ptr() as usize));
+            drop(evicted);
+        }
         unsafe { (self.vtable.as_ref().cleanup_fn)(self.data) }
     }
 }
@@ -146,6 +234,8 @@ where
         cleanup_fn: TypeWrapper(Self::cleanup_fn),
         clone_fn: TypeWrapper(Self::clone_fn),
         as_str_fn: TypeWrapper(Self::as_str_fn),
+        source_fn: None,
+        category_fn: None,
     };
 
     unsafe extern "C-unwind" fn cleanup_fn(data: Option<NonNull<ErrorInfoData>>) {
@@ -166,9 +256,196 @@ where
     }
 }
 
+/// A cause chain link materialized from a [`std::error::Error`].
+///
+/// [`CausedError`] stores its cause as a type-erased `Box<dyn Error>`, which
+/// is not `Clone` in general. `ErrorLeaf` stringifies the whole remaining
+/// chain once, up front, via [`ErrorLeaf::from_std`], but shares every link
+/// behind an `Arc` rather than a `Box`, so walking the chain through
+/// [`ErrorInfo::source`] and cloning links along the way (as
+/// [`ErrorInfo::source`]'s `'static` return type requires) only ever clones
+/// an `Arc` handle instead of re-copying the remaining sub-chain at every
+/// step.
+#[derive(Clone)]
+struct ErrorLeaf {
+    message: String,
+    source: Option<Arc<ErrorLeaf>>,
+}
+
+impl ErrorLeaf {
+    fn from_std(err: &(dyn std::error::Error + 'static)) -> Self {
+        Self {
+            message: err.to_string(),
+            source: err.source().map(|source| Arc::new(Self::from_std(source))),
+        }
+    }
+}
+
+impl Display for ErrorLeaf {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Debug for ErrorLeaf {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for ErrorLeaf {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source
+            .as_deref()
+            .map(|source| source as &(dyn std::error::Error + 'static))
+    }
+}
+
+impl AsErrorInfoVTable for Box<ErrorLeaf> {
+    const VTABLE: ErrorInfoVTable = ErrorInfoVTable {
+        cleanup_fn: TypeWrapper(Self::cleanup_fn),
+        clone_fn: TypeWrapper(Self::clone_fn),
+        as_str_fn: TypeWrapper(Self::as_str_fn),
+        source_fn: Some(TypeWrapper(Self::source_fn)),
+        category_fn: None,
+    };
+
+    unsafe extern "C-unwind" fn cleanup_fn(data: Option<NonNull<ErrorInfoData>>) {
+        drop(Box::<ErrorLeaf>::from_raw(data.unwrap().cast().as_ptr()))
+    }
+
+    unsafe extern "C-unwind" fn clone_fn(
+        data: Option<NonNullConst<ErrorInfoData>>,
+    ) -> Option<NonNull<ErrorInfoData>> {
+        let new: Box<ErrorLeaf> = Box::new(data.unwrap().cast::<ErrorLeaf>().as_ref().clone());
+        Some(NonNull::from(Box::leak(new)).cast())
+    }
+
+    unsafe extern "C-unwind" fn as_str_fn(
+        data: Option<NonNullConst<ErrorInfoData>>,
+    ) -> ErrorString {
+        ErrorString::from(data.unwrap().cast::<ErrorLeaf>().as_ref().message.as_str())
+    }
+
+    unsafe extern "C-unwind" fn source_fn(
+        data: Option<NonNullConst<ErrorInfoData>>,
+    ) -> Option<ErrorInfo> {
+        let leaf = data
+            .unwrap()
+            .cast::<ErrorLeaf>()
+            .as_ref()
+            .source
+            .as_deref()?;
+        Some(ErrorInfo::from(Box::new(leaf.clone())))
+    }
+}
+
+/// Pairs a Rust error with a [`std::error::Error`] cause, ready to become an
+/// [`ErrorInfo`] whose [`ErrorInfo::source`] chain mirrors `cause`'s.
+///
+/// `error`'s message (via [`AsRef<str>`]) is what [`ErrorInfo::as_str`]
+/// returns; `category` is what [`ErrorInfo::category`] returns.
+pub struct CausedError<T> {
+    error: T,
+    cause: Box<dyn std::error::Error + Send + 'static>,
+    category: i32,
+    /// Lazily-stringified `cause`, shared cheaply once computed so that
+    /// repeated `source_fn` calls don't re-walk the whole remaining chain.
+    cause_leaf: OnceLock<Arc<ErrorLeaf>>,
+}
+
+impl<T> CausedError<T> {
+    /// Pairs `error` with `cause`. The category defaults to `0`; set it with
+    /// [`CausedError::with_category`].
+    pub fn new(error: T, cause: impl std::error::Error + Send + 'static) -> Self {
+        Self {
+            error,
+            cause: Box::new(cause),
+            category: 0,
+            cause_leaf: OnceLock::new(),
+        }
+    }
+
+    /// Sets the numeric category returned by [`ErrorInfo::category`],
+    /// conventionally one of the `FnId`-aligned ranges (e.g. `200..300` for
+    /// library errors).
+    pub fn with_category(mut self, category: i32) -> Self {
+        self.category = category;
+        self
+    }
+
+    /// Returns the stringified `cause`, computing and caching it on first
+    /// use.
+    fn cause_leaf(&self) -> &Arc<ErrorLeaf> {
+        self.cause_leaf
+            .get_or_init(|| Arc::new(ErrorLeaf::from_std(self.cause.as_ref())))
+    }
+}
+
+impl<T> AsErrorInfoVTable for Box<CausedError<T>>
+where
+    T: AsRef<str> + Clone + Send,
+{
+    const VTABLE: ErrorInfoVTable = ErrorInfoVTable {
+        cleanup_fn: TypeWrapper(Self::cleanup_fn),
+        clone_fn: TypeWrapper(Self::clone_fn),
+        as_str_fn: TypeWrapper(Self::as_str_fn),
+        source_fn: Some(TypeWrapper(Self::source_fn)),
+        category_fn: Some(TypeWrapper(Self::category_fn)),
+    };
+
+    unsafe extern "C-unwind" fn cleanup_fn(data: Option<NonNull<ErrorInfoData>>) {
+        drop(Box::<CausedError<T>>::from_raw(
+            data.unwrap().cast().as_ptr(),
+        ))
+    }
+
+    unsafe extern "C-unwind" fn clone_fn(
+        data: Option<NonNullConst<ErrorInfoData>>,
+    ) -> Option<NonNull<ErrorInfoData>> {
+        let this = data.unwrap().cast::<CausedError<T>>();
+        let leaf = this.as_ref().cause_leaf().clone();
+        let cause_leaf = OnceLock::new();
+        let _ = cause_leaf.set(leaf.clone());
+        let new = Box::new(CausedError {
+            error: this.as_ref().error.clone(),
+            cause: Box::new((*leaf).clone()),
+            category: this.as_ref().category,
+            cause_leaf,
+        });
+        Some(NonNull::from(Box::leak(new)).cast())
+    }
+
+    unsafe extern "C-unwind" fn as_str_fn(
+        data: Option<NonNullConst<ErrorInfoData>>,
+    ) -> ErrorString {
+        ErrorString::from(
+            data.unwrap()
+                .cast::<CausedError<T>>()
+                .as_ref()
+                .error
+                .as_ref(),
+        )
+    }
+
+    unsafe extern "C-unwind" fn source_fn(
+        data: Option<NonNullConst<ErrorInfoData>>,
+    ) -> Option<ErrorInfo> {
+        let this = data.unwrap().cast::<CausedError<T>>();
+        let leaf = this.as_ref().cause_leaf();
+        Some(ErrorInfo::from(Box::new((**leaf).clone())))
+    }
+
+    unsafe extern "C-unwind" fn category_fn(data: Option<NonNullConst<ErrorInfoData>>) -> i32 {
+        data.unwrap().cast::<CausedError<T>>().as_ref().category
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::errors::ErrorInfo;
+    use crate::errors::{CausedError, ErrorInfo};
+    use std::error::Error;
+    use std::fmt::{Display, Formatter};
 
     #[test]
     fn box_error() {
@@ -178,4 +455,40 @@ mod tests {
         assert_eq!(*error_info.as_ref(), **error_str);
         assert_eq!(*error_info.clone().as_ref(), **error_str);
     }
+
+    #[derive(Debug)]
+    struct RootCause;
+
+    impl Display for RootCause {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            f.write_str("root cause")
+        }
+    }
+
+    impl Error for RootCause {}
+
+    #[test]
+    fn caused_error_source_chain() {
+        let error = CausedError::new("top-level failure", RootCause).with_category(200);
+        let error_info = ErrorInfo::from(Box::new(error));
+
+        assert_eq!(error_info.as_ref(), "top-level failure");
+        assert_eq!(error_info.category(), 200);
+
+        let source = error_info.source().expect("missing source");
+        assert_eq!(source.to_string(), "root cause");
+        assert!(source.source().is_none());
+    }
+
+    #[test]
+    fn caused_error_clone_deep_copies_source() {
+        let error = CausedError::new("top-level failure", RootCause);
+        let error_info = ErrorInfo::from(Box::new(error));
+        let cloned = error_info.clone();
+
+        assert_eq!(
+            cloned.source().expect("missing source").to_string(),
+            "root cause"
+        );
+    }
 }